@@ -0,0 +1,437 @@
+// Copyright 2020-2022 by all parties listed in the NOTICE file
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+//! Parser for the node definition config.
+//!
+//! The config is a plain INI style file made of repeated `[trustee]`,
+//! `[steward]` and `[node]` sections with `key = value` lines. Lines
+//! starting with `#` or `;` are comments.
+
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug)]
+pub struct ConfigError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "line {}: {}", self.line, self.message)
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+fn error<T>(line: usize, message: impl Into<String>) -> Result<T, ConfigError> {
+    Err(ConfigError {
+        line,
+        message: message.into(),
+    })
+}
+
+/// A DID written to the domain ledger (trustee or steward).
+#[derive(Debug)]
+pub struct Identity {
+    /// Optional, stewards in existing genesis files usually have none.
+    pub alias: Option<String>,
+    pub did: String,
+    pub verkey: String,
+}
+
+#[derive(Debug)]
+pub struct Node {
+    pub alias: String,
+    /// DID of the steward owning the node, must match a `[steward]` section.
+    pub steward: String,
+    /// Base58 encoded ed25519 verkey of the node.
+    pub dest: String,
+    pub node_ip: String,
+    pub node_port: u16,
+    pub client_ip: String,
+    pub client_port: u16,
+    pub blskey: String,
+    pub blskey_pop: String,
+}
+
+#[derive(Debug, Default)]
+pub struct Config {
+    pub trustees: Vec<Identity>,
+    pub stewards: Vec<Identity>,
+    pub nodes: Vec<Node>,
+}
+
+struct Section {
+    kind: String,
+    line: usize,
+    values: HashMap<String, String>,
+}
+
+impl Section {
+    fn take(&mut self, key: &str) -> Result<String, ConfigError> {
+        match self.values.remove(key) {
+            Some(value) if !value.is_empty() => Ok(value),
+            _ => error(
+                self.line,
+                format!("[{}] section is missing `{}`", self.kind, key),
+            ),
+        }
+    }
+
+    fn take_optional(&mut self, key: &str) -> Option<String> {
+        self.values.remove(key).filter(|value| !value.is_empty())
+    }
+
+    fn take_port(&mut self, key: &str) -> Result<u16, ConfigError> {
+        let value = self.take(key)?;
+        match value.parse() {
+            Ok(port) => Ok(port),
+            Err(_) => error(
+                self.line,
+                format!("`{}` is not a valid port: {}", key, value),
+            ),
+        }
+    }
+
+    fn finish(self) -> Result<(), ConfigError> {
+        let mut unknown: Vec<_> = self.values.keys().cloned().collect();
+        if unknown.is_empty() {
+            return Ok(());
+        }
+        unknown.sort();
+        error(
+            self.line,
+            format!(
+                "[{}] section has unknown keys: {}",
+                self.kind,
+                unknown.join(", ")
+            ),
+        )
+    }
+
+    fn into_identity(mut self) -> Result<Identity, ConfigError> {
+        let identity = Identity {
+            alias: self.take_optional("alias"),
+            did: self.take("did")?,
+            verkey: self.take("verkey")?,
+        };
+        self.finish()?;
+        Ok(identity)
+    }
+
+    fn into_node(mut self) -> Result<Node, ConfigError> {
+        let node = Node {
+            alias: self.take("alias")?,
+            steward: self.take("steward")?,
+            dest: self.take("dest")?,
+            node_ip: self.take("node_ip")?,
+            node_port: self.take_port("node_port")?,
+            client_ip: self.take("client_ip")?,
+            client_port: self.take_port("client_port")?,
+            blskey: self.take("blskey")?,
+            blskey_pop: self.take("blskey_pop")?,
+        };
+        self.finish()?;
+        Ok(node)
+    }
+}
+
+fn parse_sections(input: &str) -> Result<Vec<Section>, ConfigError> {
+    let mut sections: Vec<Section> = Vec::new();
+    for (index, raw) in input.lines().enumerate() {
+        let line_no = index + 1;
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix('[') {
+            let kind = match header.strip_suffix(']') {
+                Some(kind) => kind.trim(),
+                None => return error(line_no, "unterminated section header"),
+            };
+            if !matches!(kind, "trustee" | "steward" | "node") {
+                return error(line_no, format!("unknown section [{}]", kind));
+            }
+            sections.push(Section {
+                kind: kind.to_string(),
+                line: line_no,
+                values: HashMap::new(),
+            });
+            continue;
+        }
+        let (key, value) = match line.split_once('=') {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => return error(line_no, "expected `key = value`"),
+        };
+        let section = match sections.last_mut() {
+            Some(section) => section,
+            None => return error(line_no, "key outside of a section"),
+        };
+        if section
+            .values
+            .insert(key.to_string(), value.to_string())
+            .is_some()
+        {
+            return error(line_no, format!("duplicate key `{}`", key));
+        }
+    }
+    Ok(sections)
+}
+
+impl Config {
+    pub fn parse(input: &str) -> Result<Config, ConfigError> {
+        let mut config = Config::default();
+        for section in parse_sections(input)? {
+            match section.kind.as_str() {
+                "trustee" => config.trustees.push(section.into_identity()?),
+                "steward" => config.stewards.push(section.into_identity()?),
+                _ => config.nodes.push(section.into_node()?),
+            }
+        }
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.trustees.is_empty() {
+            return error(0, "at least one [trustee] is required");
+        }
+        if self.nodes.is_empty() {
+            return error(0, "at least one [node] is required");
+        }
+        let mut dids = Vec::new();
+        for identity in self.trustees.iter().chain(&self.stewards) {
+            if dids.contains(&identity.did.as_str()) {
+                return error(0, format!("DID {} is defined more than once", identity.did));
+            }
+            dids.push(identity.did.as_str());
+        }
+        let mut aliases = Vec::new();
+        let mut dests = Vec::new();
+        let mut addresses = Vec::new();
+        for node in &self.nodes {
+            if aliases.contains(&node.alias.as_str()) {
+                return error(
+                    0,
+                    format!("node alias {} is defined more than once", node.alias),
+                );
+            }
+            aliases.push(node.alias.as_str());
+            if dests.contains(&node.dest.as_str()) {
+                return error(
+                    0,
+                    format!("node {} reuses verkey {}", node.alias, node.dest),
+                );
+            }
+            dests.push(node.dest.as_str());
+            // node and client stacks of all nodes must listen on distinct addresses
+            for address in [
+                format!("{}:{}", node.node_ip, node.node_port),
+                format!("{}:{}", node.client_ip, node.client_port),
+            ] {
+                if addresses.contains(&address) {
+                    return error(0, format!("node {} reuses address {}", node.alias, address));
+                }
+                addresses.push(address);
+            }
+            if !self
+                .stewards
+                .iter()
+                .any(|steward| steward.did == node.steward)
+            {
+                return error(
+                    0,
+                    format!(
+                        "node {} references unknown steward {}",
+                        node.alias, node.steward
+                    ),
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Config;
+
+    const TRUSTEE: &str = "[trustee]\nalias = Trustee1\ndid = V4SGRU86Z58d6TV7PBUe6f\nverkey = ~CoRER63DVYnWZtK8uAzNbx\n";
+    const STEWARD: &str =
+        "[steward]\ndid = Th7MpTaRZVRYnPiabds81Y\nverkey = ~7TYfekw4GUagBnBVCqPjiC\n";
+    const NODE: &str = "[node]\nalias = Node1\nsteward = Th7MpTaRZVRYnPiabds81Y\n\
+        dest = Gw6pDLhcBcoQesN72qfotTgFa7cbuqZpkX3Xo6pLhPhv\n\
+        node_ip = 10.133.133.1\nnode_port = 9701\nclient_ip = 10.133.133.1\nclient_port = 9702\n\
+        blskey = blskey\nblskey_pop = blskey_pop\n";
+
+    fn config(sections: &[&str]) -> String {
+        sections.join("\n")
+    }
+
+    fn parse_error(input: &str) -> String {
+        Config::parse(input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn parses_valid_config() {
+        let config = Config::parse(&config(&["# comment", TRUSTEE, STEWARD, NODE])).unwrap();
+        assert_eq!(config.trustees[0].alias.as_deref(), Some("Trustee1"));
+        assert_eq!(config.stewards[0].alias, None);
+        assert_eq!(config.nodes[0].node_port, 9701);
+        assert_eq!(config.nodes[0].client_port, 9702);
+    }
+
+    #[test]
+    fn missing_key() {
+        let node = NODE.replace("dest = Gw6pDLhcBcoQesN72qfotTgFa7cbuqZpkX3Xo6pLhPhv\n", "");
+        assert_eq!(
+            parse_error(&config(&[TRUSTEE, STEWARD, &node])),
+            "line 10: [node] section is missing `dest`"
+        );
+    }
+
+    #[test]
+    fn unknown_key() {
+        let steward = format!("{}role = 2\n", STEWARD);
+        assert_eq!(
+            parse_error(&config(&[TRUSTEE, &steward, NODE])),
+            "line 6: [steward] section has unknown keys: role"
+        );
+    }
+
+    #[test]
+    fn duplicate_key() {
+        let trustee = format!("{}did = V4SGRU86Z58d6TV7PBUe6f\n", TRUSTEE);
+        assert_eq!(
+            parse_error(&config(&[&trustee, STEWARD, NODE])),
+            "line 5: duplicate key `did`"
+        );
+    }
+
+    #[test]
+    fn bad_port() {
+        let node = NODE.replace("node_port = 9701", "node_port = 97010");
+        assert_eq!(
+            parse_error(&config(&[TRUSTEE, STEWARD, &node])),
+            "line 10: `node_port` is not a valid port: 97010"
+        );
+    }
+
+    #[test]
+    fn key_outside_section() {
+        assert_eq!(
+            parse_error(&config(&["alias = Trustee1", TRUSTEE])),
+            "line 1: key outside of a section"
+        );
+    }
+
+    #[test]
+    fn unknown_section() {
+        assert_eq!(
+            parse_error("[client]\n"),
+            "line 1: unknown section [client]"
+        );
+    }
+
+    #[test]
+    fn unknown_steward_reference() {
+        let node = NODE.replace(
+            "steward = Th7MpTaRZVRYnPiabds81Y",
+            "steward = V4SGRU86Z58d6TV7PBUe6f",
+        );
+        assert_eq!(
+            parse_error(&config(&[TRUSTEE, STEWARD, &node])),
+            "node Node1 references unknown steward V4SGRU86Z58d6TV7PBUe6f"
+        );
+    }
+
+    #[test]
+    fn duplicate_did() {
+        let steward = STEWARD.replace("Th7MpTaRZVRYnPiabds81Y", "V4SGRU86Z58d6TV7PBUe6f");
+        let node = NODE.replace("Th7MpTaRZVRYnPiabds81Y", "V4SGRU86Z58d6TV7PBUe6f");
+        assert_eq!(
+            parse_error(&config(&[TRUSTEE, &steward, &node])),
+            "DID V4SGRU86Z58d6TV7PBUe6f is defined more than once"
+        );
+    }
+
+    #[test]
+    fn duplicate_node_alias() {
+        assert_eq!(
+            parse_error(&config(&[TRUSTEE, STEWARD, NODE, NODE])),
+            "node alias Node1 is defined more than once"
+        );
+    }
+
+    #[test]
+    fn duplicate_node_dest() {
+        let node2 = NODE
+            .replace("Node1", "Node2")
+            .replace("10.133.133.1", "10.133.133.2");
+        assert_eq!(
+            parse_error(&config(&[TRUSTEE, STEWARD, NODE, &node2])),
+            "node Node2 reuses verkey Gw6pDLhcBcoQesN72qfotTgFa7cbuqZpkX3Xo6pLhPhv"
+        );
+    }
+
+    #[test]
+    fn duplicate_node_address() {
+        let node2 = NODE
+            .replace("Node1", "Node2")
+            .replace("Gw6pDLhc", "8ECVSk17")
+            .replace("client_ip = 10.133.133.1", "client_ip = 10.133.133.2");
+        assert_eq!(
+            parse_error(&config(&[TRUSTEE, STEWARD, NODE, &node2])),
+            "node Node2 reuses address 10.133.133.1:9701"
+        );
+    }
+
+    #[test]
+    fn duplicate_client_address() {
+        let node2 = NODE
+            .replace("Node1", "Node2")
+            .replace("Gw6pDLhc", "8ECVSk17")
+            .replace("node_ip = 10.133.133.1", "node_ip = 10.133.133.2");
+        assert_eq!(
+            parse_error(&config(&[TRUSTEE, STEWARD, NODE, &node2])),
+            "node Node2 reuses address 10.133.133.1:9702"
+        );
+    }
+
+    #[test]
+    fn node_and_client_stack_on_same_address() {
+        let node = NODE.replace("client_port = 9702", "client_port = 9701");
+        assert_eq!(
+            parse_error(&config(&[TRUSTEE, STEWARD, &node])),
+            "node Node1 reuses address 10.133.133.1:9701"
+        );
+    }
+
+    #[test]
+    fn requires_trustee_and_node() {
+        assert_eq!(
+            parse_error(&config(&[STEWARD, NODE])),
+            "at least one [trustee] is required"
+        );
+        assert_eq!(
+            parse_error(&config(&[TRUSTEE, STEWARD])),
+            "at least one [node] is required"
+        );
+    }
+}
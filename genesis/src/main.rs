@@ -0,0 +1,98 @@
+// Copyright 2020-2022 by all parties listed in the NOTICE file
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+mod config;
+mod txn;
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::{env, fs};
+
+use config::Config;
+
+const POOL_GENESIS: &str = "pool_transactions_genesis";
+const DOMAIN_GENESIS: &str = "domain_transactions_genesis";
+
+fn usage() -> String {
+    format!(
+        "Usage: {} CONFIG [--out-dir DIR]\n\n\
+         Writes {} and {} for the nodes, stewards and trustees\n\
+         defined in CONFIG to DIR (defaults to the current directory).",
+        env!("CARGO_PKG_NAME"),
+        POOL_GENESIS,
+        DOMAIN_GENESIS
+    )
+}
+
+struct Args {
+    config: PathBuf,
+    out_dir: PathBuf,
+}
+
+/// Returns `Ok(None)` when only the usage was requested.
+fn parse_args() -> Result<Option<Args>, String> {
+    let mut config = None;
+    let mut out_dir = PathBuf::from(".");
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", usage());
+                return Ok(None);
+            }
+            "-o" | "--out-dir" => match args.next() {
+                Some(dir) => out_dir = PathBuf::from(dir),
+                None => return Err(format!("{} requires a directory\n\n{}", arg, usage())),
+            },
+            _ if config.is_none() && !arg.starts_with('-') => config = Some(PathBuf::from(arg)),
+            _ => return Err(format!("unexpected argument {}\n\n{}", arg, usage())),
+        }
+    }
+    match config {
+        Some(config) => Ok(Some(Args { config, out_dir })),
+        None => Err(usage()),
+    }
+}
+
+fn run(args: Option<Args>) -> Result<(), String> {
+    let args = match args {
+        Some(args) => args,
+        None => return Ok(()),
+    };
+    let input = fs::read_to_string(&args.config)
+        .map_err(|e| format!("cannot read {}: {}", args.config.display(), e))?;
+    let config = Config::parse(&input).map_err(|e| format!("{}: {}", args.config.display(), e))?;
+
+    fs::create_dir_all(&args.out_dir)
+        .map_err(|e| format!("cannot create {}: {}", args.out_dir.display(), e))?;
+    for (name, content) in [
+        (POOL_GENESIS, txn::pool_genesis(&config)),
+        (DOMAIN_GENESIS, txn::domain_genesis(&config)),
+    ] {
+        let path = args.out_dir.join(name);
+        fs::write(&path, content).map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+        println!("[OK]\t wrote {}", path.display());
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match parse_args().and_then(run) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
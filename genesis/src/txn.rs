@@ -0,0 +1,265 @@
+// Copyright 2020-2022 by all parties listed in the NOTICE file
+//
+//   Licensed under the Apache License, Version 2.0 (the "License");
+//   you may not use this file except in compliance with the License.
+//   You may obtain a copy of the License at
+//
+//       http://www.apache.org/licenses/LICENSE-2.0
+//
+//   Unless required by applicable law or agreed to in writing, software
+//   distributed under the License is distributed on an "AS IS" BASIS,
+//   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//   See the License for the specific language governing permissions and
+//   limitations under the License.
+
+//! Renders genesis transactions in the same layout as
+//! `generate_indy_pool_transactions`: one JSON object per line with sorted keys
+//! and everything outside printable ASCII escaped, like Python's `json.dumps`.
+
+use sha2::{Digest, Sha256};
+
+use crate::config::{Config, Identity, Node};
+
+const NYM: &str = "1";
+const NODE: &str = "0";
+const ROLE_TRUSTEE: &str = "0";
+const ROLE_STEWARD: &str = "2";
+
+fn quote(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            ' '..='~' => out.push(c),
+            c => {
+                let mut units = [0u16; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    out.push_str(&format!("\\u{:04x}", unit));
+                }
+            }
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Joins already rendered `(key, value)` pairs into a JSON object.
+/// Callers pass the keys in sorted order.
+fn object(fields: &[(&str, String)]) -> String {
+    let body: Vec<String> = fields
+        .iter()
+        .map(|(key, value)| format!("{}:{}", quote(key), value))
+        .collect();
+    format!("{{{}}}", body.join(","))
+}
+
+fn nym_txn(identity: &Identity, role: &str, from: Option<&str>, seq_no: usize) -> String {
+    let metadata = match from {
+        Some(did) => object(&[("from", quote(did))]),
+        None => object(&[]),
+    };
+    let mut data = Vec::new();
+    if let Some(alias) = &identity.alias {
+        data.push(("alias", quote(alias)));
+    }
+    data.push(("dest", quote(&identity.did)));
+    data.push(("role", quote(role)));
+    data.push(("verkey", quote(&identity.verkey)));
+    let txn = object(&[
+        ("data", object(&data)),
+        ("metadata", metadata),
+        ("type", quote(NYM)),
+    ]);
+    object(&[
+        ("reqSignature", object(&[])),
+        ("txn", txn),
+        ("txnMetadata", object(&[("seqNo", seq_no.to_string())])),
+        ("ver", quote("1")),
+    ])
+}
+
+/// Node transaction ids are the hex encoded SHA-256 hash of the node alias.
+fn txn_id(alias: &str) -> String {
+    Sha256::digest(alias.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn node_txn(node: &Node, seq_no: usize) -> String {
+    let data = object(&[
+        ("alias", quote(&node.alias)),
+        ("blskey", quote(&node.blskey)),
+        ("blskey_pop", quote(&node.blskey_pop)),
+        ("client_ip", quote(&node.client_ip)),
+        ("client_port", quote(&node.client_port.to_string())),
+        ("node_ip", quote(&node.node_ip)),
+        ("node_port", quote(&node.node_port.to_string())),
+        ("services", format!("[{}]", quote("VALIDATOR"))),
+    ]);
+    let txn = object(&[
+        (
+            "data",
+            object(&[("data", data), ("dest", quote(&node.dest))]),
+        ),
+        ("metadata", object(&[("from", quote(&node.steward))])),
+        ("type", quote(NODE)),
+    ]);
+    object(&[
+        ("reqSignature", object(&[])),
+        ("txn", txn),
+        (
+            "txnMetadata",
+            object(&[
+                ("seqNo", seq_no.to_string()),
+                ("txnId", quote(&txn_id(&node.alias))),
+            ]),
+        ),
+        ("ver", quote("1")),
+    ])
+}
+
+/// Trustees come first, followed by the stewards they onboard.
+pub fn domain_genesis(config: &Config) -> String {
+    let first_trustee = config.trustees.first().map(|trustee| trustee.did.as_str());
+    let trustees = config
+        .trustees
+        .iter()
+        .map(|trustee| (trustee, ROLE_TRUSTEE, None));
+    let stewards = config
+        .stewards
+        .iter()
+        .map(|steward| (steward, ROLE_STEWARD, first_trustee));
+    trustees
+        .chain(stewards)
+        .enumerate()
+        .map(|(index, (identity, role, from))| nym_txn(identity, role, from, index + 1) + "\n")
+        .collect()
+}
+
+pub fn pool_genesis(config: &Config) -> String {
+    config
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(index, node)| node_txn(node, index + 1) + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SSI4DE_POOL: &str = include_str!("../../run/lib_indy/ssi4de/pool_transactions_genesis");
+    const SSI4DE_DOMAIN: &str =
+        include_str!("../../run/lib_indy/ssi4de/domain_transactions_genesis");
+
+    fn ssi4de_line(genesis: &str, seq_no: usize) -> &str {
+        genesis
+            .lines()
+            .nth(seq_no - 1)
+            .unwrap()
+            .trim_end_matches('\r')
+    }
+
+    fn identity(alias: Option<&str>, did: &str, verkey: &str) -> Identity {
+        Identity {
+            alias: alias.map(String::from),
+            did: did.to_string(),
+            verkey: verkey.to_string(),
+        }
+    }
+
+    #[test]
+    fn quote_escapes_like_python_json() {
+        // json.dumps("Zürich\b\f\x7f\U0001F600")
+        assert_eq!(
+            quote("Z\u{fc}rich\u{8}\u{c}\u{7f}\u{1f600}"),
+            r#""Z\u00fcrich\b\f\u007f\ud83d\ude00""#
+        );
+        assert_eq!(quote("a\"b\\c\n\u{1}"), r#""a\"b\\c\n\u0001""#);
+    }
+
+    #[test]
+    fn txn_id_is_hash_of_alias() {
+        assert_eq!(
+            txn_id("MainIncubator"),
+            "0a35c047f19448f12a0c2961d8e3085140b8149d87291acbdaf56ed956900d48"
+        );
+    }
+
+    #[test]
+    fn ssi4de_node() {
+        let node = Node {
+            alias: "MainIncubator".to_string(),
+            steward: "2MZYuPv2Km7Q1eD4GCsSb6".to_string(),
+            dest: "EQ2oCtWpMdUCzKgQ2hvbyQ2i6VyJuQF9LcpHRfiah6rW".to_string(),
+            node_ip: "35.207.81.15".to_string(),
+            node_port: 9701,
+            client_ip: "35.207.81.15".to_string(),
+            client_port: 9702,
+            blskey: "14MPfxHnRvCL5dnbpD3SjxekAGnKyjkmifDFLnMJAvHcvEAarDLWX6KawhubPujSmmUMqRmQfPQzBambrdUqBGgHDryDS6Aj8BioWRwZkyTcH1zzkVQ7vZjE5S6ri5epkiS1VkVX42Z22z4cohdHVuKTgeQB5mjAwi55xNeRxpxhrNb".to_string(),
+            blskey_pop: "QoqRe3vMPr5Bt1qHDstYewWkxb5VEdugnvumLCWpkZAeUpdqjjaSTSKKJtT7U7aCe8CmShGSdCZFWs9hcpmMSGfMnmon7R99UTJj4jEr48smyxo1UpBcgooznc8N8USkZ8hSec3bRbN9y6NppuaFzfgt1WmJ3cdFi9L1Wbj1fNs5CK".to_string(),
+        };
+        assert_eq!(node_txn(&node, 1), ssi4de_line(SSI4DE_POOL, 1));
+    }
+
+    #[test]
+    fn ssi4de_trustee() {
+        let trustee = identity(
+            Some("MainIncubator"),
+            "TR4GVJf3ssSeKv85pUBZxV",
+            "~AQXCkWZAcoRERQBwDJp8Uh",
+        );
+        assert_eq!(
+            nym_txn(&trustee, ROLE_TRUSTEE, None, 1),
+            ssi4de_line(SSI4DE_DOMAIN, 1)
+        );
+    }
+
+    #[test]
+    fn ssi4de_steward() {
+        let steward = identity(None, "2MZYuPv2Km7Q1eD4GCsSb6", "~SzMxKFUXebygrfhAiVTmoV");
+        assert_eq!(
+            nym_txn(&steward, ROLE_STEWARD, Some("D4cvUdqKzjxcPewRrx9xXX"), 6),
+            ssi4de_line(SSI4DE_DOMAIN, 6)
+        );
+    }
+
+    #[test]
+    fn stewards_follow_trustees_and_are_added_by_first_trustee() {
+        let config = Config {
+            trustees: vec![
+                identity(
+                    Some("Trustee1"),
+                    "V4SGRU86Z58d6TV7PBUe6f",
+                    "~CoRER63DVYnWZtK8uAzNbx",
+                ),
+                identity(
+                    Some("Trustee2"),
+                    "LnXR1rPnncTPZvRdmJKhJQ",
+                    "~RTBtVN3iwcFoeBqPN6C4WG",
+                ),
+            ],
+            stewards: vec![identity(
+                None,
+                "Th7MpTaRZVRYnPiabds81Y",
+                "~7TYfekw4GUagBnBVCqPjiC",
+            )],
+            nodes: Vec::new(),
+        };
+        let genesis = domain_genesis(&config);
+        let lines: Vec<&str> = genesis.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].contains(r#""dest":"LnXR1rPnncTPZvRdmJKhJQ","role":"0""#));
+        assert!(lines[2].contains(r#""metadata":{"from":"V4SGRU86Z58d6TV7PBUe6f"}"#));
+        assert!(lines[2].ends_with(r#""txnMetadata":{"seqNo":3},"ver":"1"}"#));
+    }
+}
@@ -0,0 +1,102 @@
+#!/usr/bin/env bash
+
+# Derives the node verkey and BLS key (with proof of possession) from the node seed by running
+# init_indy_keys in a throw-away node container, and prints them as a [node] section for the
+# genesis file generator in ../genesis.
+
+# This file is part of https://github.com/hyperledger/indy-node-container .
+# Copyright 2021-2022 by all people listed in https://github.com/hyperledger/indy-node-container/blob/main/NOTICE , see
+# https://github.com/hyperledger/indy-node-container/blob/main/LICENSE for the license information.
+
+set -e
+
+usage() {
+    echo
+    echo "Usage:"
+    echo -n "INDY_NODE_NAME=[node alias, defaults to .env] IMAGE=[node image, defaults to .env] "
+    echo -n "INDY_NODE_PORT=[node port, defaults to .env or 9701] INDY_CLIENT_PORT=[client port, defaults to .env or 9702] "
+    echo -n "INDY_NODE_SEED=[32 character seed, only used if there is no .node.env] "
+    echo -n "STEWARD_DID=[DID of the node's steward] NODE_IP=[public node ip] CLIENT_IP=[public client ip] "
+    echo "$0"
+    echo
+    echo "The seed is read from .node.env. If there is no .node.env, INDY_NODE_SEED or a new random seed"
+    echo "is stored there, so the node started with docker-compose uses the same keys later on."
+    echo "The seed is never printed, securely backup .node.env instead."
+    echo
+    echo "The [node] section is written to stdout. NODE_IP and CLIENT_IP are the public addresses other"
+    echo "nodes and clients connect to, not the bind addresses from .env."
+    echo
+}
+
+if [[ "$1" == "-h" || "$1" == "--help" ]]; then
+    usage
+    exit 0
+fi
+
+# values from the environment win over .env
+NAME=$INDY_NODE_NAME
+NODE_IMAGE=$IMAGE
+NODE_PORT=$INDY_NODE_PORT
+CLIENT_PORT=$INDY_CLIENT_PORT
+SEED=$INDY_NODE_SEED
+[[ -f .env ]] && source .env
+INDY_NODE_NAME=${NAME:-$INDY_NODE_NAME}
+IMAGE=${NODE_IMAGE:-$IMAGE}
+INDY_NODE_PORT=${NODE_PORT:-${INDY_NODE_PORT:-9701}}
+INDY_CLIENT_PORT=${CLIENT_PORT:-${INDY_CLIENT_PORT:-9702}}
+
+for VAR in INDY_NODE_NAME IMAGE STEWARD_DID NODE_IP CLIENT_IP; do
+    if [[ -z "${!VAR}" ]]; then
+        echo -e "[FAIL]\t $VAR has to be set" >&2
+        usage >&2
+        exit 1
+    fi
+done
+
+if [[ -f .node.env ]]; then
+    INDY_NODE_SEED=
+    source .node.env
+    if [[ -z "$INDY_NODE_SEED" ]]; then
+        echo -e "[FAIL]\t .node.env does not contain INDY_NODE_SEED" >&2
+        exit 1
+    fi
+    if [[ -n "$SEED" && "$SEED" != "$INDY_NODE_SEED" ]]; then
+        echo -e "[FAIL]\t INDY_NODE_SEED differs from the seed in .node.env, refusing to overwrite .node.env" >&2
+        exit 1
+    fi
+    echo -e "[OK]\t using the seed from .node.env" >&2
+else
+    if [[ -z "$SEED" ]]; then
+        echo -e "[...]\t no seed given, generating a random seed" >&2
+        SEED=$(head -c 32 /dev/random | base64 | head -c 32)
+    fi
+    (umask 077 && echo "INDY_NODE_SEED=$SEED" > .node.env)
+    echo -e "[OK]\t stored the seed in .node.env, please securely backup this file" >&2
+fi
+
+echo -e "[...]\t running init_indy_keys for $INDY_NODE_NAME using $IMAGE" >&2
+OUTPUT=$(docker run --rm -e INDY_NODE_NAME="$INDY_NODE_NAME" --env-file .node.env "$IMAGE" \
+    /bin/bash -c 'init_indy_keys --name "$INDY_NODE_NAME" --seed "$INDY_NODE_SEED"' | grep -v -i seed)
+
+VERKEY=$(echo "$OUTPUT" | sed -n 's/^Verification key is //p' | tail -n 1)
+BLSKEY=$(echo "$OUTPUT" | sed -n 's/^BLS Public key is //p' | tail -n 1)
+BLSKEY_POP=$(echo "$OUTPUT" | sed -n 's/^Proof of possession for BLS key is //p' | tail -n 1)
+
+if [[ -z "$VERKEY" || -z "$BLSKEY" || -z "$BLSKEY_POP" ]]; then
+    echo -e "[FAIL]\t could not parse init_indy_keys output:" >&2
+    echo "$OUTPUT" >&2
+    exit 1
+fi
+
+echo -e "[OK]\t done" >&2
+
+echo "[node]"
+echo "alias = $INDY_NODE_NAME"
+echo "steward = $STEWARD_DID"
+echo "dest = $VERKEY"
+echo "node_ip = $NODE_IP"
+echo "node_port = $INDY_NODE_PORT"
+echo "client_ip = $CLIENT_IP"
+echo "client_port = $INDY_CLIENT_PORT"
+echo "blskey = $BLSKEY"
+echo "blskey_pop = $BLSKEY_POP"